@@ -4,6 +4,7 @@
 //! option. This file may not be copied, modified, or distributed
 //! except according to those terms.
 
+use std::cmp::Ordering;
 use std::kinds;
 use std::mem;
 use std::num;
@@ -13,8 +14,6 @@ use std::fmt;
 /// Similar to the slice iterator, but with a certain number of steps
 /// (stride) skipped per iteration.
 ///
-/// Does not support zero-sized `A`.
-///
 /// Iterator element type is `&'a A`
 pub struct Stride<'a, A> {
     // begin is NULL when the iterator is exhausted, because
@@ -50,12 +49,17 @@ impl<'a, A> Stride<'a, A>
     pub fn from_slice(xs: &'a [A], step: uint) -> Stride<'a, A>
     {
         assert!(step != 0);
-        assert!(mem::size_of::<A>() != 0);
-        let mut begin = ptr::null();
-        let mut end = ptr::null();
         let (d, r) = num::div_rem(xs.len(), step);
         let nelem = d + if r > 0 { 1 } else { 0 };
         unsafe {
+            // Zero-sized `A` has no real address space to stride over, so
+            // `begin` stays the slice's (dangling but non-null) base pointer
+            // and `end` is repurposed to hold the remaining element count.
+            if mem::size_of::<A>() == 0 {
+                return Stride::from_ptrs(xs.as_ptr(), mem::transmute(nelem), step as int);
+            }
+            let mut begin = ptr::null();
+            let mut end = ptr::null();
             if nelem != 0 {
                 begin = xs.as_ptr();
                 end = begin.offset(((nelem - 1) * step) as int);
@@ -80,9 +84,15 @@ impl<'a, A> Stride<'a, A>
     }
 
     /// Create Stride iterator from an existing Stride iterator
+    ///
+    /// Does not support restriding a `Stride` over a zero-sized `A`: with
+    /// no real addresses to subdivide, and `end` already repurposed to
+    /// hold the remaining element count, there is nothing meaningful to
+    /// restride.
     pub fn from_stride(it: Stride<'a, A>, step: uint) -> Stride<'a, A>
     {
         assert!(step != 0);
+        assert!(mem::size_of::<A>() != 0);
         let newstride = it.stride * (step as int);
         unsafe {
             let nelem = ((it.end.to_uint() as int) - (it.begin.to_uint() as int))
@@ -97,11 +107,44 @@ impl<'a, A> Stride<'a, A>
     /// in effect reversing the iterator.
     #[inline]
     pub fn swap_ends(&mut self) {
+        // For zero-sized `A`, `end` holds the remaining element count
+        // rather than an address, and every element aliases `begin`
+        // anyway, so reversing has no observable effect.
+        if mem::size_of::<A>() == 0 {
+            return;
+        }
         if !self.begin.is_null() {
             mem::swap(&mut self.begin, &mut self.end);
             self.stride = -self.stride;
         }
     }
+
+    /// Split the iterator in two at index `mid`: the first `Stride`
+    /// yields the first `mid` elements, the second yields the rest.
+    pub fn split_at(self, mid: uint) -> (Stride<'a, A>, Stride<'a, A>)
+    {
+        let len = self.size_hint().val0();
+        assert!(mid <= len);
+        (self.slice(0, mid), self.slice(mid, len))
+    }
+
+    /// A sub-range `[from, to)` of this iterator, as a new `Stride` over
+    /// the same underlying elements with the same stride.
+    pub fn slice(&self, from: uint, to: uint) -> Stride<'a, A>
+    {
+        assert!(from <= to && to <= self.size_hint().val0());
+        unsafe {
+            if mem::size_of::<A>() == 0 {
+                return Stride::from_ptrs(self.begin, mem::transmute(to - from), self.stride);
+            }
+            if from == to {
+                return Stride::from_ptrs(ptr::null(), ptr::null(), self.stride);
+            }
+            let begin = self.begin.offset(self.stride * (from as int));
+            let end = self.begin.offset(self.stride * ((to - 1) as int));
+            Stride::from_ptrs(begin, end, self.stride)
+        }
+    }
 }
 
 impl<'a, A> MutStride<'a, A>
@@ -117,12 +160,17 @@ impl<'a, A> MutStride<'a, A>
     pub fn from_mut_slice(xs: &'a mut [A], step: uint) -> MutStride<'a, A>
     {
         assert!(step != 0);
-        assert!(mem::size_of::<A>() != 0);
-        let mut begin = ptr::mut_null();
-        let mut end = ptr::mut_null();
         let (d, r) = num::div_rem(xs.len(), step);
         let nelem = d + if r > 0 { 1 } else { 0 };
         unsafe {
+            // Zero-sized `A` has no real address space to stride over, so
+            // `begin` stays the slice's (dangling but non-null) base pointer
+            // and `end` is repurposed to hold the remaining element count.
+            if mem::size_of::<A>() == 0 {
+                return MutStride::from_ptrs(xs.as_mut_ptr(), mem::transmute(nelem), step as int);
+            }
+            let mut begin = ptr::mut_null();
+            let mut end = ptr::mut_null();
             if nelem != 0 {
                 begin = xs.as_mut_ptr();
                 end = begin.offset(((nelem - 1) * step) as int);
@@ -148,9 +196,15 @@ impl<'a, A> MutStride<'a, A>
     }
 
     /// Create MutStride iterator from an existing MutStride iterator
+    ///
+    /// Does not support restriding a `MutStride` over a zero-sized `A`:
+    /// with no real addresses to subdivide, and `end` already repurposed
+    /// to hold the remaining element count, there is nothing meaningful
+    /// to restride.
     pub fn from_mut_stride(it: MutStride<'a, A>, step: uint) -> MutStride<'a, A>
     {
         assert!(step != 0);
+        assert!(mem::size_of::<A>() != 0);
         let newstride = it.stride * (step as int);
         unsafe {
             let nelem = ((it.end.to_uint() as int) - (it.begin.to_uint() as int))
@@ -165,12 +219,290 @@ impl<'a, A> MutStride<'a, A>
     /// in effect reversing the iterator.
     #[inline]
     pub fn swap_ends(&mut self) {
+        // For zero-sized `A`, `end` holds the remaining element count
+        // rather than an address, and every element aliases `begin`
+        // anyway, so reversing has no observable effect.
+        if mem::size_of::<A>() == 0 {
+            return;
+        }
         if !self.begin.is_null() {
             mem::swap(&mut self.begin, &mut self.end);
             self.stride = -self.stride;
         }
     }
+
+    /// Split the view in two at index `mid`: the first `MutStride` yields
+    /// the first `mid` elements, the second yields the rest. The two
+    /// halves are non-overlapping, so each can safely be handed to a
+    /// different thread.
+    ///
+    /// Because this consumes `self` by value, the two halves are the
+    /// only handles left to the underlying elements: there is no way to
+    /// keep using the original `MutStride` to get a third, overlapping
+    /// view. The same guarantee holds for `slice`, which borrows `self`
+    /// mutably for the lifetime of its result instead.
+    pub fn split_at(self, mid: uint) -> (MutStride<'a, A>, MutStride<'a, A>)
+    {
+        let len = self.size_hint().val0();
+        assert!(mid <= len);
+        unsafe {
+            if mem::size_of::<A>() == 0 {
+                let left = MutStride::from_ptrs(self.begin, mem::transmute(mid), self.stride);
+                let right = MutStride::from_ptrs(self.begin, mem::transmute(len - mid), self.stride);
+                return (left, right);
+            }
+            let left = if mid == 0 {
+                MutStride::from_ptrs(ptr::mut_null(), ptr::mut_null(), self.stride)
+            } else {
+                MutStride::from_ptrs(self.begin, self.begin.offset(self.stride * ((mid - 1) as int)), self.stride)
+            };
+            let right = if mid == len {
+                MutStride::from_ptrs(ptr::mut_null(), ptr::mut_null(), self.stride)
+            } else {
+                MutStride::from_ptrs(self.begin.offset(self.stride * (mid as int)), self.end, self.stride)
+            };
+            (left, right)
+        }
+    }
+
+    /// A sub-range `[from, to)` of this view, as a new `MutStride` over
+    /// the same underlying elements with the same stride.
+    ///
+    /// Borrows `self` mutably for as long as the returned `MutStride` is
+    /// live, so the borrow checker prevents this sub-view from aliasing
+    /// the rest of `self` while both are in use.
+    pub fn slice<'b>(&'b mut self, from: uint, to: uint) -> MutStride<'b, A>
+    {
+        assert!(from <= to && to <= self.size_hint().val0());
+        unsafe {
+            if mem::size_of::<A>() == 0 {
+                return MutStride::from_ptrs(self.begin, mem::transmute(to - from), self.stride);
+            }
+            if from == to {
+                return MutStride::from_ptrs(ptr::mut_null(), ptr::mut_null(), self.stride);
+            }
+            let begin = self.begin.offset(self.stride * (from as int));
+            let end = self.begin.offset(self.stride * ((to - 1) as int));
+            MutStride::from_ptrs(begin, end, self.stride)
+        }
+    }
+
+    /// Sort the elements yielded by this view in place, according to the
+    /// given comparison function, using an in-place pattern-defeating
+    /// quicksort.
+    ///
+    /// Every reorder goes through `IndexMut`, so the physical stride
+    /// between elements is respected; no temporary buffer is used beyond
+    /// O(1) element storage for the swaps themselves.
+    pub fn sort_unstable_by<F>(&mut self, mut compare: F) where F: FnMut(&A, &A) -> Ordering
+    {
+        let len = self.size_hint().val0();
+        if len < 2 {
+            return;
+        }
+        let limit = 2 * log2(len);
+        pdqsort(self, 0, len, &mut compare, limit);
+    }
+}
+
+impl<'a, A: Ord> MutStride<'a, A>
+{
+    /// Sort the elements yielded by this view in place.
+    pub fn sort_unstable(&mut self)
+    {
+        self.sort_unstable_by(|a, b| a.cmp(b))
+    }
+}
+
+/// Swap the elements at indices `i` and `j` of a `MutStride`.
+#[inline]
+fn swap<A>(v: &mut MutStride<A>, i: uint, j: uint) {
+    if i == j {
+        return;
+    }
+    unsafe {
+        let pi: *mut A = &mut v[i];
+        let pj: *mut A = &mut v[j];
+        ptr::swap(pi, pj);
+    }
+}
+
+/// `floor(log2(n))`, used to bound quicksort recursion depth before
+/// falling back to heapsort.
+fn log2(n: uint) -> uint {
+    let mut n = n;
+    let mut log = 0u;
+    while n > 1 {
+        n >>= 1;
+        log += 1;
+    }
+    log
+}
+
+fn is_sorted<A, F>(v: &MutStride<A>, lo: uint, hi: uint, compare: &mut F) -> bool
+    where F: FnMut(&A, &A) -> Ordering
+{
+    for i in range(lo + 1, hi) {
+        if compare(&v[i - 1], &v[i]) == Ordering::Greater {
+            return false;
+        }
+    }
+    true
+}
+
+fn insertion_sort<A, F>(v: &mut MutStride<A>, lo: uint, hi: uint, compare: &mut F)
+    where F: FnMut(&A, &A) -> Ordering
+{
+    for i in range(lo + 1, hi) {
+        let mut j = i;
+        while j > lo && compare(&v[j], &v[j - 1]) == Ordering::Less {
+            swap(v, j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+fn sift_down<A, F>(v: &mut MutStride<A>, lo: uint, start: uint, len: uint, compare: &mut F)
+    where F: FnMut(&A, &A) -> Ordering
+{
+    let mut root = start;
+    loop {
+        let mut child = 2 * root + 1;
+        if child >= len {
+            break;
+        }
+        if child + 1 < len && compare(&v[lo + child], &v[lo + child + 1]) == Ordering::Less {
+            child += 1;
+        }
+        if compare(&v[lo + root], &v[lo + child]) == Ordering::Less {
+            swap(v, lo + root, lo + child);
+            root = child;
+        } else {
+            break;
+        }
+    }
+}
+
+/// Heapsort over the index range `[lo, hi)`. Used as the pdqsort fallback
+/// once the recursion depth limit is exceeded, guaranteeing O(n log n)
+/// even on adversarial inputs that would otherwise drive quicksort to
+/// O(n^2).
+fn heapsort<A, F>(v: &mut MutStride<A>, lo: uint, hi: uint, compare: &mut F)
+    where F: FnMut(&A, &A) -> Ordering
+{
+    let len = hi - lo;
+    if len < 2 {
+        return;
+    }
+
+    let mut start = len / 2;
+    loop {
+        if start == 0 {
+            break;
+        }
+        start -= 1;
+        sift_down(v, lo, start, len, compare);
+    }
+
+    let mut end = len;
+    loop {
+        end -= 1;
+        if end == 0 {
+            break;
+        }
+        swap(v, lo, lo + end);
+        sift_down(v, lo, 0, end, compare);
+    }
 }
+
+/// Sort `a`, `b`, `c` (given as indices) so that the median of the three
+/// ends up at `b`.
+fn median_of_three<A, F>(v: &mut MutStride<A>, a: uint, b: uint, c: uint, compare: &mut F)
+    where F: FnMut(&A, &A) -> Ordering
+{
+    if compare(&v[b], &v[a]) == Ordering::Less {
+        swap(v, a, b);
+    }
+    if compare(&v[c], &v[b]) == Ordering::Less {
+        swap(v, b, c);
+        if compare(&v[b], &v[a]) == Ordering::Less {
+            swap(v, a, b);
+        }
+    }
+}
+
+/// Hoare partition of `[lo, hi)` around the pivot already placed at `lo`.
+/// Returns the pivot's final index and whether the range needed no
+/// swaps at all (a hint that it was already partitioned/sorted).
+fn partition<A, F>(v: &mut MutStride<A>, lo: uint, hi: uint, compare: &mut F) -> (uint, bool)
+    where F: FnMut(&A, &A) -> Ordering
+{
+    let mut i = lo;
+    let mut j = hi - 1;
+    let mut any_swap = false;
+    loop {
+        i += 1;
+        while i < hi && compare(&v[i], &v[lo]) == Ordering::Less {
+            i += 1;
+        }
+        while compare(&v[j], &v[lo]) == Ordering::Greater {
+            j -= 1;
+        }
+        if i >= j {
+            break;
+        }
+        swap(v, i, j);
+        any_swap = true;
+        j -= 1;
+    }
+    swap(v, lo, j);
+    (j, !any_swap)
+}
+
+/// Pattern-defeating quicksort over the index range `[lo, hi)`: plain
+/// insertion sort for small ranges, median-of-three (ninther for large
+/// ranges) pivot selection with Hoare partitioning, falling back to
+/// heapsort once `limit` recursive partitions have been spent without
+/// the range collapsing (a sign of an adversarial input).
+fn pdqsort<A, F>(v: &mut MutStride<A>, lo: uint, hi: uint, compare: &mut F, limit: uint)
+    where F: FnMut(&A, &A) -> Ordering
+{
+    if hi - lo < 2 {
+        return;
+    }
+
+    if hi - lo <= 20 {
+        insertion_sort(v, lo, hi, compare);
+        return;
+    }
+
+    if limit == 0 {
+        heapsort(v, lo, hi, compare);
+        return;
+    }
+
+    let mid = lo + (hi - lo) / 2;
+    if hi - lo > 128 {
+        let s = (hi - lo) / 8;
+        median_of_three(v, lo, lo + s, lo + 2 * s, compare);
+        median_of_three(v, mid - s, mid, mid + s, compare);
+        median_of_three(v, hi - 1 - 2 * s, hi - 1 - s, hi - 1, compare);
+        median_of_three(v, lo + s, mid, hi - 1 - s, compare);
+    } else {
+        median_of_three(v, lo, mid, hi - 1, compare);
+    }
+    swap(v, lo, mid);
+
+    let (split, was_partitioned) = partition(v, lo, hi, compare);
+
+    if was_partitioned && is_sorted(v, lo, hi, compare) {
+        return;
+    }
+
+    pdqsort(v, lo, split, compare, limit - 1);
+    pdqsort(v, split + 1, hi, compare, limit - 1);
+}
+
 macro_rules! stride_iterator {
     (struct $name:ident -> $ptr:ty, $elem:ty, $null:expr) => {
         impl<'a, A> Iterator<$elem> for $name<'a, A>
@@ -178,7 +510,15 @@ macro_rules! stride_iterator {
             #[inline]
             fn next(&mut self) -> Option<$elem>
             {
-                if self.begin.is_null() {
+                if mem::size_of::<A>() == 0 {
+                    let remaining = self.end.to_uint();
+                    if remaining == 0 {
+                        None
+                    } else {
+                        self.end = unsafe { mem::transmute(remaining - 1) };
+                        Some(unsafe { mem::transmute(self.begin) })
+                    }
+                } else if self.begin.is_null() {
                     None
                 } else {
                     unsafe {
@@ -196,7 +536,9 @@ macro_rules! stride_iterator {
             fn size_hint(&self) -> (uint, Option<uint>)
             {
                 let len;
-                if self.begin.is_null() {
+                if mem::size_of::<A>() == 0 {
+                    len = self.end.to_uint() as int;
+                } else if self.begin.is_null() {
                     len = 0;
                 } else {
                     len = (self.end as uint - self.begin as uint) as int / self.stride
@@ -212,7 +554,15 @@ macro_rules! stride_iterator {
             #[inline]
             fn next_back(&mut self) -> Option<$elem>
             {
-                if self.begin.is_null() {
+                if mem::size_of::<A>() == 0 {
+                    let remaining = self.end.to_uint();
+                    if remaining == 0 {
+                        None
+                    } else {
+                        self.end = unsafe { mem::transmute(remaining - 1) };
+                        Some(unsafe { mem::transmute(self.begin) })
+                    }
+                } else if self.begin.is_null() {
                     None
                 } else {
                     unsafe {
@@ -247,6 +597,18 @@ macro_rules! stride_iterator {
 stride_iterator!{struct Stride -> *const A, &'a A, ptr::null()}
 stride_iterator!{struct MutStride -> *mut A, &'a mut A, ptr::mut_null()}
 
+impl<'a, A> IndexMut<uint, A> for MutStride<'a, A>
+{
+    fn index_mut<'b>(&'b mut self, i: &uint) -> &'b mut A
+    {
+        assert!(*i < self.size_hint().val0());
+        unsafe {
+            let ptr = self.begin.offset(self.stride * (*i as int));
+            mem::transmute(ptr)
+        }
+    }
+}
+
 impl<'a, A: fmt::Show> fmt::Show for Stride<'a, A>
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
@@ -270,3 +632,311 @@ impl<'a, A> Clone for Stride<'a, A>
         *self
     }
 }
+
+/// A read-only 2D view over a flat, row-major buffer, giving zero-copy
+/// access to its rows, columns and diagonal as `Stride` iterators.
+pub struct MatrixView<'a, A> {
+    data: &'a [A],
+    rows: uint,
+    cols: uint,
+}
+
+impl<'a, A> MatrixView<'a, A>
+{
+    /// Create a matrix view over `data`, a flat row-major buffer of
+    /// `rows * cols` elements.
+    pub fn new(data: &'a [A], rows: uint, cols: uint) -> MatrixView<'a, A>
+    {
+        assert!(data.len() == rows * cols);
+        MatrixView { data: data, rows: rows, cols: cols }
+    }
+
+    /// Number of rows.
+    pub fn rows(&self) -> uint { self.rows }
+
+    /// Number of columns.
+    pub fn cols(&self) -> uint { self.cols }
+
+    /// A contiguous `Stride` over row `i`.
+    pub fn row(&self, i: uint) -> Stride<'a, A>
+    {
+        assert!(i < self.rows);
+        let start = i * self.cols;
+        Stride::from_slice(self.data.slice(start, start + self.cols), 1)
+    }
+
+    /// A `Stride` with step `cols` over column `j`.
+    pub fn column(&self, j: uint) -> Stride<'a, A>
+    {
+        assert!(j < self.cols);
+        Stride::from_slice(self.data.slice_from(j), self.cols)
+    }
+
+    /// A `Stride` with step `cols + 1` over the main diagonal.
+    pub fn diagonal(&self) -> Stride<'a, A>
+    {
+        let step = self.cols + 1;
+        let n = if self.rows < self.cols { self.rows } else { self.cols };
+        let len = if n == 0 { 0 } else { (n - 1) * step + 1 };
+        Stride::from_slice(self.data.slice(0, len), step)
+    }
+
+    /// An iterator that yields each column in turn, as if iterating the
+    /// transpose of this matrix row by row.
+    pub fn transpose_iter(&self) -> TransposeIter<'a, A>
+    {
+        TransposeIter { view: *self, col: 0 }
+    }
+}
+
+impl<'a, A> Clone for MatrixView<'a, A>
+{
+    fn clone(&self) -> MatrixView<'a, A>
+    {
+        *self
+    }
+}
+
+/// Iterator over the columns of a `MatrixView`, yielded in order. See
+/// `MatrixView::transpose_iter`.
+pub struct TransposeIter<'a, A> {
+    view: MatrixView<'a, A>,
+    col: uint,
+}
+
+impl<'a, A> Iterator<Stride<'a, A>> for TransposeIter<'a, A>
+{
+    fn next(&mut self) -> Option<Stride<'a, A>>
+    {
+        if self.col >= self.view.cols {
+            None
+        } else {
+            let col = self.view.column(self.col);
+            self.col += 1;
+            Some(col)
+        }
+    }
+}
+
+/// A mutable 2D view over a flat, row-major buffer, giving zero-copy
+/// mutable access to its rows, columns and diagonal as `MutStride`
+/// iterators.
+pub struct MatrixViewMut<'a, A> {
+    data: &'a mut [A],
+    rows: uint,
+    cols: uint,
+}
+
+impl<'a, A> MatrixViewMut<'a, A>
+{
+    /// Create a mutable matrix view over `data`, a flat row-major buffer
+    /// of `rows * cols` elements.
+    pub fn new(data: &'a mut [A], rows: uint, cols: uint) -> MatrixViewMut<'a, A>
+    {
+        assert!(data.len() == rows * cols);
+        MatrixViewMut { data: data, rows: rows, cols: cols }
+    }
+
+    /// Number of rows.
+    pub fn rows(&self) -> uint { self.rows }
+
+    /// Number of columns.
+    pub fn cols(&self) -> uint { self.cols }
+
+    /// A contiguous `MutStride` over row `i`.
+    pub fn row<'b>(&'b mut self, i: uint) -> MutStride<'b, A>
+    {
+        assert!(i < self.rows);
+        let cols = self.cols;
+        let start = i * cols;
+        MutStride::from_mut_slice(self.data.mut_slice(start, start + cols), 1)
+    }
+
+    /// A `MutStride` with step `cols` over column `j`.
+    pub fn column<'b>(&'b mut self, j: uint) -> MutStride<'b, A>
+    {
+        assert!(j < self.cols);
+        let cols = self.cols;
+        MutStride::from_mut_slice(self.data.mut_slice_from(j), cols)
+    }
+
+    /// A `MutStride` with step `cols + 1` over the main diagonal.
+    pub fn diagonal<'b>(&'b mut self) -> MutStride<'b, A>
+    {
+        let step = self.cols + 1;
+        let n = if self.rows < self.cols { self.rows } else { self.cols };
+        let len = if n == 0 { 0 } else { (n - 1) * step + 1 };
+        MutStride::from_mut_slice(self.data.mut_slice(0, len), step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MatrixView, MatrixViewMut, MutStride, Stride};
+
+    #[test]
+    fn zst_stride_from_slice_len() {
+        let xs = [(), (), (), (), (), (), ()];
+        let it = Stride::from_slice(xs.as_slice(), 2);
+        assert_eq!(it.len(), 4); // ceil(7 / 2)
+        assert_eq!(it.count(), 4);
+    }
+
+    #[test]
+    fn zst_stride_next_and_next_back() {
+        let xs = [(), (), (), (), ()];
+        let mut it = Stride::from_slice(xs.as_slice(), 1);
+        assert_eq!(it.len(), 5);
+        assert!(it.next().is_some());
+        assert!(it.next_back().is_some());
+        assert_eq!(it.len(), 3);
+        assert_eq!(it.count(), 3);
+    }
+
+    #[test]
+    fn zst_stride_rev() {
+        let xs = [(), (), (), ()];
+        let it = Stride::from_slice(xs.as_slice(), 1);
+        assert_eq!(it.rev().count(), 4);
+    }
+
+    #[test]
+    fn zst_mut_stride_from_mut_slice_len() {
+        let mut xs = [(), (), (), (), (), ()];
+        let mut it = MutStride::from_mut_slice(xs.as_mut_slice(), 3);
+        assert_eq!(it.len(), 2); // ceil(6 / 3)
+        assert!(it.next().is_some());
+        assert!(it.next().is_some());
+        assert!(it.next().is_none());
+    }
+
+    #[test]
+    fn split_at_mut_writes_land_in_correct_slots() {
+        let mut data = Vec::new();
+        for i in range(0i, 10) {
+            data.push(i);
+        }
+        {
+            let view = MutStride::from_mut_slice(data.as_mut_slice(), 1);
+            let (mut left, mut right) = view.split_at(4);
+            for i in range(0u, left.size_hint().val0()) {
+                left[i] = 100 + i as int;
+            }
+            for i in range(0u, right.size_hint().val0()) {
+                right[i] = 200 + i as int;
+            }
+        }
+        let mut expected = Vec::new();
+        for i in range(0i, 4) {
+            expected.push(100 + i);
+        }
+        for i in range(0i, 6) {
+            expected.push(200 + i);
+        }
+        assert_eq!(data, expected);
+    }
+
+    fn check_sort(xs: &[int]) {
+        let mut data = xs.to_vec();
+        let mut expected = xs.to_vec();
+        expected.sort();
+        {
+            let mut view = MutStride::from_mut_slice(data.as_mut_slice(), 1);
+            view.sort_unstable();
+        }
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn sort_unstable_small() {
+        check_sort([5i, 3, 1, 4, 2].as_slice());
+    }
+
+    #[test]
+    fn sort_unstable_empty_and_singleton() {
+        check_sort([].as_slice());
+        check_sort([1i].as_slice());
+    }
+
+    #[test]
+    fn sort_unstable_already_sorted() {
+        let mut xs = Vec::new();
+        for i in range(0i, 200) {
+            xs.push(i);
+        }
+        check_sort(xs.as_slice());
+    }
+
+    #[test]
+    fn sort_unstable_reverse_sorted() {
+        let mut xs = Vec::new();
+        for i in range(0i, 200) {
+            xs.push(200 - i);
+        }
+        check_sort(xs.as_slice());
+    }
+
+    #[test]
+    fn sort_unstable_duplicates_heavy() {
+        let mut xs = Vec::new();
+        for i in range(0u, 200) {
+            xs.push((i % 5) as int);
+        }
+        check_sort(xs.as_slice());
+    }
+
+    #[test]
+    fn sort_unstable_alternating_low_high() {
+        // Values are taken alternately from the low and high end of a
+        // sorted run (0, 511, 1, 510, ...). The ninther pivot selection
+        // used for ranges this large keeps the recursion well inside
+        // pdqsort's depth limit, so this exercises the quicksort path,
+        // not the heapsort fallback; it checks the result is sorted.
+        let n = 512u;
+        let mut killer = Vec::with_capacity(n);
+        let mut lo = 0i;
+        let mut hi = n as int;
+        let mut take_lo = true;
+        while lo < hi {
+            if take_lo {
+                killer.push(lo);
+                lo += 1;
+            } else {
+                hi -= 1;
+                killer.push(hi);
+            }
+            take_lo = !take_lo;
+        }
+        check_sort(killer.as_slice());
+    }
+
+    #[test]
+    fn diagonal_non_square_wide() {
+        let data = [0i, 1, 2, 3, 4, 5, 6, 7];
+        let view = MatrixView::new(data.as_slice(), 2, 4);
+        let diag: Vec<int> = view.diagonal().map(|&x| x).collect();
+        assert_eq!(diag.len(), 2); // min(rows, cols)
+        assert_eq!(diag, vec![0i, 5]);
+    }
+
+    #[test]
+    fn diagonal_non_square_tall() {
+        let data = [0i, 1, 2, 3, 4, 5, 6, 7];
+        let view = MatrixView::new(data.as_slice(), 4, 2);
+        let diag: Vec<int> = view.diagonal().map(|&x| x).collect();
+        assert_eq!(diag.len(), 2); // min(rows, cols)
+        assert_eq!(diag, vec![0i, 3]);
+    }
+
+    #[test]
+    fn diagonal_mut_non_square() {
+        let mut data = [0i, 1, 2, 3, 4, 5, 6, 7];
+        {
+            let mut view = MatrixViewMut::new(data.as_mut_slice(), 2, 4);
+            for x in view.diagonal() {
+                *x = 0;
+            }
+        }
+        assert_eq!(data.as_slice(), [0i, 1, 2, 3, 4, 0, 6, 7].as_slice());
+    }
+}